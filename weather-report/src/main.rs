@@ -1,5 +1,7 @@
-use rand::distributions::{Distribution, Uniform};
-use rand::Rng;
+use rand::distributions::{Distribution, Uniform, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::io::Write;
 
 // Enum pour les stations (au moins 5)
 #[derive(Copy, Clone, Debug)]
@@ -12,16 +14,6 @@ enum StationType {
 }
 
 impl StationType {
-    fn to_string(&self) -> String {
-        match self {
-            StationType::StationA => "StationA".to_string(),
-            StationType::StationB => "StationB".to_string(),
-            StationType::StationC => "StationC".to_string(),
-            StationType::StationD => "StationD".to_string(),
-            StationType::StationE => "StationE".to_string(),
-        }
-    }
-
     fn all() -> [StationType; 5] {
         [
             StationType::StationA,
@@ -33,72 +25,652 @@ impl StationType {
     }
 }
 
+impl std::fmt::Display for StationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            StationType::StationA => "StationA",
+            StationType::StationB => "StationB",
+            StationType::StationC => "StationC",
+            StationType::StationD => "StationD",
+            StationType::StationE => "StationE",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Liste des noms de station reconnus, pour les messages d'erreur et la documentation.
+const STATION_NAME_LIST: &str = "StationA|StationB|StationC|StationD|StationE";
+
+// Erreur renvoyée par `StationType::from_str` quand le nom ne correspond à aucune station.
+#[derive(Debug)]
+struct ParseStationError(String);
+
+impl std::fmt::Display for ParseStationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "station inconnue: \"{}\" (attendu l'une de {STATION_NAME_LIST})",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseStationError {}
+
+impl std::str::FromStr for StationType {
+    type Err = ParseStationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "StationA" => Ok(StationType::StationA),
+            "StationB" => Ok(StationType::StationB),
+            "StationC" => Ok(StationType::StationC),
+            "StationD" => Ok(StationType::StationD),
+            "StationE" => Ok(StationType::StationE),
+            other => Err(ParseStationError(other.to_string())),
+        }
+    }
+}
+
+// Les colonnes à émettre/attendre pour un `WeatherRecord`. `Extended` ajoute
+// humidité et champs dérivés; `Minimal` conserve le format historique à 4 colonnes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RecordSchema {
+    Minimal,
+    Extended,
+}
+
 // Struct pour un enregistrement météo
+#[derive(Debug)]
 struct WeatherRecord {
     date: String,          // YYYY-MM-DD
     station: StationType,  // enum
     temperature: f32,      // Celsius
     pressure: f32,         // hPa
+    humidity: Option<u8>,  // 0-100%, présent seulement pour RecordSchema::Extended
+    feels_like: Option<f32>,
+    temp_min: Option<f32>,
+    temp_max: Option<f32>,
 }
 
 impl WeatherRecord {
     fn to_csv_line(&self) -> String {
-        format!(
+        let base = format!(
             "{},{},{:.1},{:.1}",
             self.date,
-            self.station.to_string(),
+            self.station,
             self.temperature,
             self.pressure
-        )
+        );
+        match (self.humidity, self.feels_like, self.temp_min, self.temp_max) {
+            (Some(humidity), Some(feels_like), Some(temp_min), Some(temp_max)) => {
+                format!("{base},{humidity},{feels_like:.1},{temp_min:.1},{temp_max:.1}")
+            }
+            _ => base,
+        }
+    }
+
+    // Inverse de `to_csv_line`: reparse une ligne CSV (sans l'en-tête) en `WeatherRecord`.
+    // Le schéma (minimal ou étendu) est déduit du nombre de colonnes.
+    fn from_csv_line(line: &str) -> Result<WeatherRecord, ParseError> {
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != 4 && fields.len() != 8 {
+            return Err(ParseError::WrongFieldCount(fields.len()));
+        }
+
+        let date = parse_date(fields[0])?;
+        let station: StationType = parse_field(&fields, 1, ParseError::InvalidStation)?;
+        let temperature = parse_field::<f32>(&fields, 2, ParseError::InvalidTemperature)?;
+        let pressure = parse_field::<f32>(&fields, 3, ParseError::InvalidPressure)?;
+
+        match fields.len() {
+            4 => Ok(WeatherRecord {
+                date,
+                station,
+                temperature,
+                pressure,
+                humidity: None,
+                feels_like: None,
+                temp_min: None,
+                temp_max: None,
+            }),
+            8 => {
+                let humidity = parse_field::<u8>(&fields, 4, ParseError::InvalidHumidity)?;
+                let feels_like = parse_field::<f32>(&fields, 5, ParseError::InvalidFeelsLike)?;
+                let temp_min = parse_field::<f32>(&fields, 6, ParseError::InvalidTempMin)?;
+                let temp_max = parse_field::<f32>(&fields, 7, ParseError::InvalidTempMax)?;
+                Ok(WeatherRecord {
+                    date,
+                    station,
+                    temperature,
+                    pressure,
+                    humidity: Some(humidity),
+                    feels_like: Some(feels_like),
+                    temp_min: Some(temp_min),
+                    temp_max: Some(temp_max),
+                })
+            }
+            _ => unreachable!("la longueur de `fields` a déjà été validée"),
+        }
+    }
+}
+
+// Parse la colonne `index` de `fields` avec `FromStr`, en reportant le texte brut de
+// la colonne (vide si elle est absente) dans l'erreur si elle est absente ou invalide.
+fn parse_field<T: std::str::FromStr>(
+    fields: &[&str],
+    index: usize,
+    err: fn(String) -> ParseError,
+) -> Result<T, ParseError> {
+    let raw = fields.get(index).copied().unwrap_or("");
+    raw.parse().map_err(|_| err(raw.to_string()))
+}
+
+// Reparse une date `YYYY-MM-DD` en validant le mois et le jour (bissextiles inclus).
+fn parse_date(s: &str) -> Result<String, ParseError> {
+    let parts: Vec<&str> = s.split('-').collect();
+    let [y, m, d] = parts[..] else {
+        return Err(ParseError::InvalidDate(s.to_string()));
+    };
+    let year: i32 = y.parse().map_err(|_| ParseError::InvalidDate(s.to_string()))?;
+    let month: u32 = m.parse().map_err(|_| ParseError::InvalidDate(s.to_string()))?;
+    let day: u32 = d.parse().map_err(|_| ParseError::InvalidDate(s.to_string()))?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return Err(ParseError::InvalidDate(s.to_string()));
     }
+    Ok(format!("{year:04}-{month:02}-{day:02}"))
 }
 
-// Génère une date aléatoire (2020-2025), mois 1-12, jour en fonction du mois
-fn generate_random_date<R: Rng + ?Sized>(rng: &mut R) -> String {
-    let year = Uniform::from(2020..=2025).sample(rng);
-    let month = Uniform::from(1..=12).sample(rng);
-    let max_day = match month {
+// Erreur de reparsing d'une ligne CSV ou d'un fichier de `WeatherRecord`.
+#[derive(Debug)]
+enum ParseError {
+    WrongFieldCount(usize),
+    InvalidDate(String),
+    InvalidStation(String),
+    InvalidTemperature(String),
+    InvalidPressure(String),
+    InvalidHumidity(String),
+    InvalidFeelsLike(String),
+    InvalidTempMin(String),
+    InvalidTempMax(String),
+    Io(std::io::Error),
+    AtLine(usize, Box<ParseError>),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::WrongFieldCount(found) => {
+                write!(f, "attendu 4 (minimal) ou 8 (étendu) champs, trouvé {found}")
+            }
+            ParseError::InvalidDate(s) => write!(f, "date invalide: {s}"),
+            ParseError::InvalidStation(s) => write!(f, "station inconnue: {s}"),
+            ParseError::InvalidTemperature(s) => write!(f, "température invalide: {s}"),
+            ParseError::InvalidPressure(s) => write!(f, "pression invalide: {s}"),
+            ParseError::InvalidHumidity(s) => write!(f, "humidité invalide: {s}"),
+            ParseError::InvalidFeelsLike(s) => write!(f, "ressenti invalide: {s}"),
+            ParseError::InvalidTempMin(s) => write!(f, "temp_min invalide: {s}"),
+            ParseError::InvalidTempMax(s) => write!(f, "temp_max invalide: {s}"),
+            ParseError::Io(e) => write!(f, "erreur d'entrée/sortie: {e}"),
+            ParseError::AtLine(line, source) => write!(f, "ligne {line}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<std::io::Error> for ParseError {
+    fn from(e: std::io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+// Lit un fichier CSV produit par ce générateur (en-tête ignoré) et reparse chaque ligne.
+fn read_records<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<WeatherRecord>, ParseError> {
+    let content = std::fs::read_to_string(path)?;
+    let mut records = Vec::new();
+
+    for (i, line) in content.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = WeatherRecord::from_csv_line(line)
+            .map_err(|e| ParseError::AtLine(i + 1, Box::new(e)))?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+// Erreur renvoyée quand les bornes passées à `WeatherDist::new` sont incohérentes.
+#[derive(Debug)]
+enum WeatherDistError {
+    InvalidTemperatureRange,
+    InvalidPressureRange,
+    InvalidDateRange,
+    NoStations,
+    InvalidWeights,
+}
+
+impl std::fmt::Display for WeatherDistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeatherDistError::InvalidTemperatureRange => {
+                write!(f, "temp_min doit être strictement inférieur à temp_max")
+            }
+            WeatherDistError::InvalidPressureRange => {
+                write!(f, "pressure_min doit être strictement inférieur à pressure_max")
+            }
+            WeatherDistError::InvalidDateRange => {
+                write!(f, "la date/année de début doit précéder ou égaler celle de fin")
+            }
+            WeatherDistError::NoStations => write!(f, "il faut au moins une station"),
+            WeatherDistError::InvalidWeights => write!(
+                f,
+                "les poids doivent être de même longueur que les stations, positifs ou nuls, et non tous nuls"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WeatherDistError {}
+
+// Année bissextile au sens du calendrier grégorien.
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
         1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
         4 | 6 | 9 | 11 => 30,
-        2 => 28, // ignore bissextiles comme permis
-        _ => 30,
-    };
-    let day = Uniform::from(1..=max_day).sample(rng);
-    format!("{year:04}-{month:02}-{day:02}")
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => unreachable!("mois hors de 1..=12"),
+    }
+}
+
+// Convertit une date civile en un nombre de jours depuis l'epoch (1970-01-01).
+//
+// Algorithme "days_from_civil" d'Howard Hinnant (calendrier grégorien
+// proleptique), exact pour toute année `i32`.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = i64::from(year) - i64::from(month <= 2);
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400; // [0, 399]
+    let mp = (i64::from(month) + 9) % 12; // [0, 11], mars = 0
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+    era * 146097 + doe - 719468
+}
+
+// Convertit un nombre de jours depuis l'epoch en date civile (année, mois, jour).
+//
+// Inverse exacte de [`days_from_civil`] ("civil_from_days" d'Howard Hinnant).
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    let year = (y + i64::from(month <= 2)) as i32;
+    (year, month, day)
+}
+
+// Distribution qui échantillonne uniformément sur les *jours* d'une plage de
+// dates inclusive, au lieu d'échantillonner année/mois/jour indépendamment
+// (ce qui sur-pondère les mois courts et ne peut jamais produire le 29 février).
+struct UniformDate {
+    day_range: Uniform<i64>,
 }
 
-// Génère un enregistrement météo aléatoire
-fn generate_weather_record<R: Rng + ?Sized>(rng: &mut R) -> WeatherRecord {
-    let stations = StationType::all();
-    let idx = Uniform::from(0..stations.len()).sample(rng);
-    let station = stations[idx];
+impl UniformDate {
+    fn new(start: (i32, u32, u32), end: (i32, u32, u32)) -> Result<Self, WeatherDistError> {
+        let start_days = days_from_civil(start.0, start.1, start.2);
+        let end_days = days_from_civil(end.0, end.1, end.2);
+        if start_days > end_days {
+            return Err(WeatherDistError::InvalidDateRange);
+        }
+        Ok(UniformDate {
+            day_range: Uniform::from(start_days..=end_days),
+        })
+    }
+
+    // Plage couvrant des années civiles complètes, du 1er janvier de `year_min`
+    // au 31 décembre de `year_max`.
+    fn for_year_range(year_min: i32, year_max: i32) -> Result<Self, WeatherDistError> {
+        if year_min > year_max {
+            return Err(WeatherDistError::InvalidDateRange);
+        }
+        UniformDate::new((year_min, 1, 1), (year_max, 12, days_in_month(year_max, 12)))
+    }
+}
+
+impl Distribution<String> for UniformDate {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let (year, month, day) = civil_from_days(self.day_range.sample(rng));
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+// Tirage d'un indice de station, uniforme ou pondéré selon la configuration.
+enum StationSelector {
+    Uniform(Uniform<usize>),
+    Weighted(WeightedIndex<f64>),
+}
+
+impl StationSelector {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        match self {
+            StationSelector::Uniform(u) => u.sample(rng),
+            StationSelector::Weighted(w) => w.sample(rng),
+        }
+    }
+}
+
+// Distribution réutilisable qui produit des `WeatherRecord`.
+//
+// Les bornes sont validées une seule fois dans `new`, et les `Uniform`
+// internes sont précalculés pour que chaque `sample` reste bon marché.
+struct WeatherDist {
+    stations: Vec<StationType>,
+    station_range: StationSelector,
+    temperature_range: Uniform<i32>, // dixièmes de degré
+    pressure_range: Uniform<i32>,    // dixièmes d'hPa
+    date_range: UniformDate,
+    schema: RecordSchema,
+    humidity_range: Uniform<u8>,
+    spread_range: Uniform<i32>, // dixièmes de degré, écart entre temperature et temp_min/temp_max
+}
+
+// Bornes de configuration pour `WeatherDist::new`, regroupées dans un seul
+// paramètre plutôt qu'une liste d'arguments positionnels à rallonge.
+struct WeatherDistConfig {
+    temperature: (f32, f32), // (min, max) en °C
+    pressure: (f32, f32),    // (min, max) en hPa
+    years: (i32, i32),       // (min, max) inclusifs
+    stations: Vec<StationType>,
+    station_weights: Option<Vec<f64>>,
+}
+
+impl Default for WeatherDistConfig {
+    // Reprend le comportement historique du générateur: stations équiprobables.
+    fn default() -> Self {
+        WeatherDistConfig {
+            temperature: (-10.0, 40.0),
+            pressure: (980.0, 1050.0),
+            years: (2020, 2025),
+            stations: StationType::all().to_vec(),
+            station_weights: None,
+        }
+    }
+}
+
+impl WeatherDist {
+    fn new(config: WeatherDistConfig) -> Result<Self, WeatherDistError> {
+        let (temp_min, temp_max) = config.temperature;
+        let (pressure_min, pressure_max) = config.pressure;
+        let (year_min, year_max) = config.years;
+        let stations = &config.stations;
+
+        if temp_min >= temp_max {
+            return Err(WeatherDistError::InvalidTemperatureRange);
+        }
+        if pressure_min >= pressure_max {
+            return Err(WeatherDistError::InvalidPressureRange);
+        }
+        if stations.is_empty() {
+            return Err(WeatherDistError::NoStations);
+        }
+
+        let station_range = match &config.station_weights {
+            Some(weights) => {
+                if weights.len() != stations.len()
+                    || weights.iter().any(|&w| w < 0.0)
+                    || weights.iter().all(|&w| w == 0.0)
+                {
+                    return Err(WeatherDistError::InvalidWeights);
+                }
+                let weighted = WeightedIndex::new(weights)
+                    .map_err(|_| WeatherDistError::InvalidWeights)?;
+                StationSelector::Weighted(weighted)
+            }
+            None => StationSelector::Uniform(Uniform::from(0..stations.len())),
+        };
+
+        Ok(WeatherDist {
+            stations: stations.clone(),
+            station_range,
+            temperature_range: Uniform::from(
+                (temp_min * 10.0) as i32..=(temp_max * 10.0) as i32,
+            ),
+            pressure_range: Uniform::from(
+                (pressure_min * 10.0) as i32..=(pressure_max * 10.0) as i32,
+            ),
+            date_range: UniformDate::for_year_range(year_min, year_max)?,
+            schema: RecordSchema::Minimal,
+            humidity_range: Uniform::from(0..=100),
+            spread_range: Uniform::from(5..=30), // 0.5 à 3.0 degrés
+        })
+    }
+
+    // Choisit le schéma des enregistrements générés; `Extended` ajoute humidité,
+    // ressenti et min/max.
+    fn with_schema(mut self, schema: RecordSchema) -> Self {
+        self.schema = schema;
+        self
+    }
+}
 
-    let temperature = Uniform::from(-100..=400) // on génère en dixièmes pour un f32 ensuite
-        .sample(rng) as f32
-        / 10.0; // -10.0 à 40.0
+impl Distribution<WeatherRecord> for WeatherDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> WeatherRecord {
+        let station = self.stations[self.station_range.sample(rng)];
+        let temperature = self.temperature_range.sample(rng) as f32 / 10.0;
+        let pressure = self.pressure_range.sample(rng) as f32 / 10.0;
 
-    let pressure = Uniform::from(9800..=10500)
-        .sample(rng) as f32
-        / 10.0; // 980.0 à 1050.0
+        let (humidity, feels_like, temp_min, temp_max) = match self.schema {
+            RecordSchema::Minimal => (None, None, None, None),
+            RecordSchema::Extended => {
+                let humidity = self.humidity_range.sample(rng);
+                // Ressenti dérivé de la température et de l'humidité (pas tiré
+                // indépendamment) pour rester physiquement plausible: l'humidité
+                // accentue le chaud perçu par temps chaud, et le froid par temps froid.
+                let humidity_factor = (f32::from(humidity) - 50.0) / 50.0; // [-1.0, 1.0]
+                let feels_like = temperature + humidity_factor * temperature.signum() * 2.0;
+                let temp_min = temperature - self.spread_range.sample(rng) as f32 / 10.0;
+                let temp_max = temperature + self.spread_range.sample(rng) as f32 / 10.0;
+                // Arrondi à la même précision que `to_csv_line` ({:.1}) pour que
+                // `from_csv_line(rec.to_csv_line())` reproduise exactement l'original.
+                let round_tenth = |v: f32| (v * 10.0).round() / 10.0;
+                (
+                    Some(humidity),
+                    Some(round_tenth(feels_like)),
+                    Some(round_tenth(temp_min)),
+                    Some(round_tenth(temp_max)),
+                )
+            }
+        };
 
-    WeatherRecord {
-        date: generate_random_date(rng),
-        station,
-        temperature,
-        pressure,
+        WeatherRecord {
+            date: self.date_range.sample(rng),
+            station,
+            temperature,
+            pressure,
+            humidity,
+            feels_like,
+            temp_min,
+            temp_max,
+        }
+    }
+}
+
+// Options de la ligne de commande: `--seed <u64>`, `--count <n>`, `--output <path>`,
+// `--weights <w1,w2,...>` (un poids par station de `StationType::all()`, dans l'ordre),
+// `--extended` (ajoute humidité/ressenti/min/max aux colonnes émises).
+struct Cli {
+    seed: Option<u64>,
+    count: Option<usize>,
+    output: Option<String>,
+    weights: Option<Vec<f64>>,
+    extended: bool,
+}
+
+impl Cli {
+    fn parse() -> Cli {
+        let mut cli = Cli {
+            seed: None,
+            count: None,
+            output: None,
+            weights: None,
+            extended: false,
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().expect("--seed attend une valeur u64");
+                    cli.seed = Some(value.parse().expect("--seed attend une valeur u64"));
+                }
+                "--count" => {
+                    let value = args.next().expect("--count attend une valeur entière");
+                    cli.count = Some(value.parse().expect("--count attend une valeur entière"));
+                }
+                "--output" => {
+                    cli.output = Some(args.next().expect("--output attend un chemin de fichier"));
+                }
+                "--weights" => {
+                    let value = args
+                        .next()
+                        .expect("--weights attend une liste de poids séparés par des virgules");
+                    cli.weights = Some(
+                        value
+                            .split(',')
+                            .map(|w| w.trim().parse().expect("poids invalide dans --weights"))
+                            .collect(),
+                    );
+                }
+                "--extended" => cli.extended = true,
+                other => eprintln!("option inconnue ignorée: {other}"),
+            }
+        }
+        cli
     }
 }
 
 fn main() {
-    let mut rng = rand::thread_rng();
+    let cli = Cli::parse();
+
+    let config = WeatherDistConfig {
+        station_weights: cli.weights,
+        ..Default::default()
+    };
+    let schema = if cli.extended {
+        RecordSchema::Extended
+    } else {
+        RecordSchema::Minimal
+    };
+    let dist = WeatherDist::new(config)
+        .expect("--weights doit fournir un poids par station, positif ou nul, non tous nuls")
+        .with_schema(schema);
+
+    // Générateur déterministe si `--seed` est fourni, sinon puisé dans l'entropie système.
+    let mut rng: Box<dyn RngCore> = match cli.seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(StdRng::from_entropy()),
+    };
 
-    // En-tête
-    println!("Date,Station,Temperature,Pressure");
+    // Nombre d'enregistrements: `--count` sinon un tirage aléatoire entre 10 et 20
+    let n = cli
+        .count
+        .unwrap_or_else(|| Uniform::from(10..=20).sample(&mut rng));
+
+    // Écrit vers `--output` si fourni, sinon sur la sortie standard
+    let mut writer: Box<dyn Write> = match &cli.output {
+        Some(path) => Box::new(std::io::BufWriter::new(
+            std::fs::File::create(path).expect("impossible de créer le fichier de sortie"),
+        )),
+        None => Box::new(std::io::BufWriter::new(std::io::stdout())),
+    };
+
+    let header = match schema {
+        RecordSchema::Minimal => "Date,Station,Temperature,Pressure",
+        RecordSchema::Extended => {
+            "Date,Station,Temperature,Pressure,Humidity,FeelsLike,TempMin,TempMax"
+        }
+    };
+    writeln!(writer, "{header}").expect("échec d'écriture");
+    for rec in dist.sample_iter(&mut rng).take(n) {
+        writeln!(writer, "{}", rec.to_csv_line()).expect("échec d'écriture");
+    }
+    writer.flush().expect("échec d'écriture");
+    drop(writer);
 
-    // Nombre aléatoire d'enregistrements entre 10 et 20
-    let n = Uniform::from(10..=20).sample(&mut rng);
-    for _ in 0..n {
-        let rec = generate_weather_record(&mut rng);
-        println!("{}", rec.to_csv_line());
+    // Relit et reparse le fichier qu'on vient d'écrire, pour garantir qu'il est
+    // bien consommable par `read_records`/`from_csv_line` avant de rendre la main.
+    if let Some(path) = &cli.output {
+        let records = read_records(path).expect("le fichier généré n'a pas pu être relu");
+        assert_eq!(
+            records.len(),
+            n,
+            "nombre d'enregistrements relus différent de celui généré"
+        );
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_record(schema: RecordSchema) -> WeatherRecord {
+        let mut rec = WeatherDist::new(WeatherDistConfig::default())
+            .unwrap()
+            .with_schema(schema)
+            .sample(&mut StdRng::seed_from_u64(1));
+        // 2024 est bissextile: force une date du 29 février pour couvrir ce cas.
+        rec.date = "2024-02-29".to_string();
+        rec
+    }
+
+    #[test]
+    fn csv_roundtrip_minimal() {
+        let rec = sample_record(RecordSchema::Minimal);
+        let line = rec.to_csv_line();
+        let back = WeatherRecord::from_csv_line(&line).unwrap();
+        assert_eq!(back.to_csv_line(), line);
+    }
+
+    #[test]
+    fn csv_roundtrip_extended() {
+        let rec = sample_record(RecordSchema::Extended);
+        let line = rec.to_csv_line();
+        let back = WeatherRecord::from_csv_line(&line).unwrap();
+        assert_eq!(back.to_csv_line(), line);
+    }
+
+    #[test]
+    fn from_csv_line_rejects_wrong_field_count() {
+        let err = WeatherRecord::from_csv_line("2024-01-01,StationA,10.0").unwrap_err();
+        assert!(matches!(err, ParseError::WrongFieldCount(3)));
+    }
+
+    #[test]
+    fn from_csv_line_rejects_unknown_station() {
+        let err = WeatherRecord::from_csv_line("2024-01-01,StationZ,10.0,1000.0").unwrap_err();
+        assert!(matches!(err, ParseError::InvalidStation(s) if s == "StationZ"));
+    }
+
+    #[test]
+    fn read_records_skips_header_and_reports_line_number() {
+        let path = std::env::temp_dir().join("weather_report_read_records_test.csv");
+        std::fs::write(&path, "Date,Station,Temperature,Pressure\n2024-02-29,StationA,1.0,1000.0\nbogus\n").unwrap();
+
+        let err = read_records(&path).unwrap_err();
+        assert!(matches!(err, ParseError::AtLine(3, _)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}